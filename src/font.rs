@@ -0,0 +1,95 @@
+//! Custom fonts for [`crate::ocr_with`].
+//!
+//! The built-in height-6 and height-10 alphabets cover everything Advent of Code ships, but
+//! some puzzles draw ASCII-art in a non-standard size. [`FontSet`] lets a caller register its
+//! own glyph strip and use it through [`crate::ocr_with`] the same way the built-in fonts are
+//! used internally.
+
+use alloc::collections::BTreeMap;
+
+use crate::segment_glyphs;
+
+/// How consecutive glyphs in a font are packed into columns.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kerning {
+    /// Glyphs are separated by at least one blank column, as AoC's fonts normally are.
+    Spaced,
+    /// Glyphs are packed edge-to-edge in fixed-width groups of `width` columns, with no blank
+    /// separator column between them. AoC's height-6 font kerns its widest letters this way.
+    FixedWidth(usize),
+}
+
+/// A glyph alphabet: the height its glyphs are drawn at, how consecutive glyphs are kerned,
+/// and the id -> char mapping built from one or more registered glyph strips.
+///
+/// Built with [`FontSetBuilder`] and consumed by [`crate::ocr_with`].
+pub struct FontSet {
+    pub(crate) height: usize,
+    pub(crate) kerning: Kerning,
+    pub(crate) glyphs: BTreeMap<u64, char>,
+}
+
+/// Builds a [`FontSet`] by registering one or more glyph strips.
+///
+/// # Example
+///
+/// ```
+/// use advent_ocr::{FontSetBuilder, ocr_with};
+///
+/// // A height-6 font in which some letters (Y and H here) touch with no blank column between
+/// // them, so the split width has to be supplied explicitly.
+/// let glyph_strip = "\
+/// #.....##..#...##..#.###..
+/// #....#..#.#...##..#.#..#.
+/// #....#.....#.#.####.###..
+/// #....#.##...#..#..#.#..#.
+/// #....#..#...#..#..#.#..#.
+/// \x23###..###...#..#..#.###..";
+///
+/// let font = FontSetBuilder::new(6)
+///     .fixed_width(5)
+///     .register(glyph_strip, "LGYHB")
+///     .build();
+///
+/// assert_eq!(ocr_with(glyph_strip, &font), Some("LGYHB".to_string()));
+/// ```
+pub struct FontSetBuilder {
+    height: usize,
+    kerning: Kerning,
+    glyphs: BTreeMap<u64, char>,
+}
+
+impl FontSetBuilder {
+    /// Starts a builder for a font whose glyphs are `height` rows tall and separated by a
+    /// blank column. Call [`fixed_width`](Self::fixed_width) first if the font packs its
+    /// glyphs edge-to-edge instead.
+    pub fn new(height: usize) -> Self {
+        FontSetBuilder { height, kerning: Kerning::Spaced, glyphs: BTreeMap::new() }
+    }
+
+    /// Marks this font as packing glyphs into fixed-width groups of `width` columns, with no
+    /// blank separator column between them.
+    pub fn fixed_width(mut self, width: usize) -> Self {
+        self.kerning = Kerning::FixedWidth(width);
+        self
+    }
+
+    /// Registers a glyph strip — an ASCII-art alphabet, one letter per kerned group of
+    /// columns — alongside the `letters` it spells out, in order.
+    pub fn register(mut self, glyph_strip: &str, letters: &str) -> Self {
+        let glyph_strip = glyph_strip.trim();
+        if let Some(ids) = segment_glyphs(glyph_strip, self.height, self.kerning) {
+            ids.into_iter()
+                .zip(letters.chars())
+                .for_each(|((id, _width, _x_start), ch)| {
+                    self.glyphs.insert(id, ch);
+                });
+        }
+        self
+    }
+
+    /// Finishes the font.
+    pub fn build(self) -> FontSet {
+        FontSet { height: self.height, kerning: self.kerning, glyphs: self.glyphs }
+    }
+}
@@ -1,8 +1,20 @@
-//! A function to convert ASCII-art representations of letters generated by Advent of Code 
+//! A function to convert ASCII-art representations of letters generated by Advent of Code
 //! puzzles into a String containing those letters.
+//!
+//! The glyph tables are baked into the binary with `include_str!`, so there's no filesystem
+//! access on the happy path: with the default `std` feature turned off (`--no-default-features`),
+//! the crate builds as `#![no_std]` against `alloc` alone, which is what `wasm32-unknown-unknown`
+//! and other embedded targets need.
 
-use std::{collections::HashMap, env, fs};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+pub mod font;
 pub mod scannable;
+pub use crate::font::{FontSet, FontSetBuilder, Kerning};
 pub use crate::scannable::Scannable;
 
 /// Takes an image containing Advent of Code's ASCII-art letter representations and converts 
@@ -23,6 +35,7 @@ pub use crate::scannable::Scannable;
 /// # Example
 /// 
 /// ```
+/// # use advent_ocr::ocr;
 /// let image = r"
 /// .##..###...##.
 /// ##..#.#..#.#..#
@@ -31,28 +44,158 @@ pub use crate::scannable::Scannable;
 /// ##..#.#..#.#..#
 /// ##..#.###...##.
 ///     ";
-/// 
+///
 /// let s = ocr(image);
-/// assert_eq!(s, "ABC");
+/// assert_eq!(s, Some("ABC".to_string()));
 /// ```
 pub fn ocr<T: Scannable>(image: T) -> Option<String> {
+    let glyphs = ocr_detailed(image)?;
+    Some(glyphs.iter().map(|glyph| glyph.ch.unwrap_or('?')).collect())
+}
+
+/// A single recognized (or unrecognized) glyph from an [`ocr_detailed`] call.
+#[derive(Clone, Copy, Debug)]
+pub struct Glyph {
+    /// The recognized character, or `None` if no known glyph matched `id`.
+    pub ch: Option<char>,
+    /// The column range in the source image this glyph was read from.
+    pub x_start: usize,
+    pub x_end: usize,
+    /// The glyph's dimensions in columns and rows.
+    pub width: usize,
+    pub height: usize,
+    /// The raw column-bitmask id `map_to_id` folded from this glyph's pixels.
+    pub id: u64,
+}
+
+/// Like [`ocr`], but returns a [`Glyph`] per letter instead of a flattened `String`, carrying
+/// the source column range, dimensions, and raw bitmask alongside the recognized character.
+/// Useful for a debugging or visualization tool that needs to point at exactly which region of
+/// the grid failed to parse, or re-render a glyph from its bitmask.
+pub fn ocr_detailed<T: Scannable>(image: T) -> Option<Vec<Glyph>> {
     let image = image.normalize();
     let image = image.trim();
+    let (_, height) = image_dims(image)?;
     let ids = map_to_id(image)?;
     let letter_map = get_letter_map();
+
+    let glyphs = ids.into_iter()
+        .map(|(id, width, x_start)| Glyph {
+            ch: letter_map.get(&id).copied(),
+            x_start,
+            x_end: x_start + width,
+            width,
+            height,
+            id,
+        })
+        .collect();
+    Some(glyphs)
+}
+
+/// Like [`ocr`], but falls back to the nearest known glyph instead of giving up with `'?'`
+/// when a column bitmask doesn't exactly match a known one.
+///
+/// Nearness is the Hamming distance between bitmasks, `(a ^ b).count_ones()`, computed only
+/// against known glyphs of the same bit-length (`width * height`) as the unmatched one, since
+/// `map_to_id` folds `width * height` bits into the id and distances between differently
+/// sized glyphs are meaningless. A candidate is accepted only if its distance is within
+/// `max_bit_errors`; otherwise the letter stays `'?'`.
+pub fn ocr_lenient<T: Scannable>(image: T, max_bit_errors: u32) -> Option<String> {
+    let matches = lenient_matches(image, max_bit_errors)?;
+    Some(matches.into_iter().map(|(ch, _)| ch).collect())
+}
+
+/// Same as [`ocr_lenient`], but also returns the Hamming distance behind each matched letter
+/// (`0` for an exact match) so callers can judge how confident a match is.
+pub fn ocr_lenient_with_confidence<T: Scannable>(
+    image: T,
+    max_bit_errors: u32,
+) -> Option<Vec<(char, u32)>> {
+    lenient_matches(image, max_bit_errors)
+}
+
+/// Like [`ocr`], but reads glyphs from a caller-supplied [`FontSet`] instead of the built-in
+/// height-6 and height-10 alphabets. Use this to recognize a non-standard font registered
+/// with [`FontSetBuilder`].
+pub fn ocr_with<T: Scannable>(image: T, font_set: &FontSet) -> Option<String> {
+    let image = image.normalize();
+    let image = image.trim();
+    let ids = segment_glyphs(image, font_set.height, font_set.kerning)?;
     let ocr = ids.iter()
-        .map(|id| letter_map.get(id).unwrap_or(&'?'))
+        .map(|&(id, _, _)| *font_set.glyphs.get(&id).unwrap_or(&'?'))
         .collect();
     Some(ocr)
 }
 
-fn map_to_id(image: &str) -> Option<Vec<u64>> {
+fn lenient_matches<T: Scannable>(image: T, max_bit_errors: u32) -> Option<Vec<(char, u32)>> {
+    let image = image.normalize();
+    let image = image.trim();
+    let (_, height) = image_dims(image)?;
+    let ids = map_to_id(image)?;
+    let letter_map = get_letter_map();
+    let known_glyphs = get_known_glyphs();
+
+    let matches = ids.iter()
+        .map(|&(id, width, _)| match letter_map.get(&id) {
+            Some(&ch) => (ch, 0),
+            None => nearest_glyph(id, width * height, &known_glyphs, max_bit_errors),
+        })
+        .collect();
+    Some(matches)
+}
+
+fn nearest_glyph(
+    id: u64,
+    bit_length: usize,
+    known_glyphs: &[KnownGlyph],
+    max_bit_errors: u32,
+) -> (char, u32) {
+    known_glyphs.iter()
+        .filter(|glyph| glyph.bit_length as usize == bit_length)
+        .map(|glyph| (glyph.ch, (glyph.id ^ id).count_ones()))
+        .min_by_key(|&(_, distance)| distance)
+        .filter(|&(_, distance)| distance <= max_bit_errors)
+        .unwrap_or(('?', u32::MAX))
+}
+
+fn image_dims(image: &str) -> Option<(usize, usize)> {
     let width = image.find('\n')?;
     let height = image.len() / width;
+    Some((width, height))
+}
+
+/// Returns, for each glyph found in `image`, the recognized id, the column width it was
+/// folded from, and the column it started at, using the built-in fonts' own kerning rule (the
+/// height-6 font packs its widest letters into fixed-width groups of 5 columns; every other
+/// height is space-kerned).
+fn map_to_id(image: &str) -> Option<Vec<(u64, usize, usize)>> {
+    let (_, height) = image_dims(image)?;
+    let kerning = if height == 6 { Kerning::FixedWidth(5) } else { Kerning::Spaced };
+    segment_glyphs(image, height, kerning)
+}
+
+/// Splits `image` into glyphs of `height` rows, returning for each one the recognized
+/// column-bitmask id, the column width it was folded from, and the column it started at.
+///
+/// `kerning` controls where one glyph ends and the next begins: a blank column always ends a
+/// glyph, and with [`Kerning::FixedWidth`] a glyph is also cut off once it reaches `width`
+/// columns, since touching glyphs have no blank separator to split on.
+pub(crate) fn segment_glyphs(
+    image: &str,
+    height: usize,
+    kerning: Kerning,
+) -> Option<Vec<(u64, usize, usize)>> {
+    let width = image.find('\n')?;
     let image = image.as_bytes();
-    
+
+    let fixed_width = match kerning {
+        Kerning::FixedWidth(width) => Some(width),
+        Kerning::Spaced => None,
+    };
+
     let mut id = 0u64;
     let mut letter_width = 0usize;
+    let mut x_start = 0usize;
 
     let mut ids = Vec::new();
 
@@ -61,60 +204,81 @@ fn map_to_id(image: &str) -> Option<Vec<u64>> {
             .map(|y| image[x + y * (width + 1)] == b'#')
             .collect();
         if col.iter().all(|&b| !b) {
-            if id != 0 { ids.push(id); }
+            if id != 0 { ids.push((id, letter_width, x_start)); }
             id = 0;
             letter_width = 0;
         } else {
-            if height == 6 && letter_width == 5 {
-                ids.push(id);
+            if fixed_width == Some(letter_width) {
+                ids.push((id, letter_width, x_start));
                 id = 0;
                 letter_width = 0;
             }
+            if letter_width == 0 { x_start = x; }
             id = col.iter()
                 .fold(id, |acc, &b| (acc << 1) + if b { 1 } else { 0 });
             letter_width += 1;
         }
     }
-    if id != 0 { ids.push(id) };
+    if id != 0 { ids.push((id, letter_width, x_start)) };
     Some(ids)
 }
 
-fn get_letter_map() -> HashMap<u64, char> {
-    let font6 = fs::read_to_string("res/font6.txt").unwrap();
+/// A glyph from one of the built-in fonts: its column-bitmask id, the bit-length that id was
+/// folded from (`width * height`), and the character it represents.
+#[derive(Clone, Copy)]
+struct KnownGlyph {
+    id: u64,
+    bit_length: u32,
+    ch: char,
+}
+
+fn get_letter_map() -> BTreeMap<u64, char> {
+    get_known_glyphs()
+        .into_iter()
+        .map(|glyph| (glyph.id, glyph.ch))
+        .collect()
+}
+
+fn get_known_glyphs() -> Vec<KnownGlyph> {
+    let font6 = include_str!("../res/font6.txt");
     let (letters6, letter_forms6) = font6
         .split_once("\n\n")
         .unwrap();
 
-    let font10 = fs::read_to_string("res/font10.txt").unwrap();
+    let font10 = include_str!("../res/font10.txt");
     let (letters10, letter_forms10) = font10
         .split_once("\n\n")
         .unwrap();
 
-    let mut letter_map = HashMap::new();
+    let mut known_glyphs = Vec::new();
 
-    populate_letter_map(&mut letter_map, letter_forms6, letters6);
-    populate_letter_map(&mut letter_map, letter_forms10, letters10);
+    populate_known_glyphs(&mut known_glyphs, letter_forms6, letters6);
+    populate_known_glyphs(&mut known_glyphs, letter_forms10, letters10);
 
-    letter_map
+    known_glyphs
 }
 
-fn populate_letter_map(
-    letter_map: &mut HashMap<u64, char>, 
-    letter_forms: &str, 
+fn populate_known_glyphs(
+    known_glyphs: &mut Vec<KnownGlyph>,
+    letter_forms: &str,
     letters: &str
 ) {
-    map_to_id(letter_forms.trim())
+    let letter_forms = letter_forms.trim();
+    let (_, height) = image_dims(letter_forms).unwrap();
+    map_to_id(letter_forms)
         .unwrap()
-        .iter()
-        .zip(letters.chars()) 
-        .for_each(|(&id, c)| {
-            letter_map.insert(id, c);
+        .into_iter()
+        .zip(letters.chars())
+        .for_each(|((id, width, _), ch)| {
+            known_glyphs.push(KnownGlyph { id, bit_length: (width * height) as u32, ch });
         });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
+
     fn ocr_test<T: Scannable>(output: &str, letter_forms: T) -> bool {
         Some(output.to_string()) == ocr(letter_forms)
     }
@@ -166,6 +330,87 @@ mod tests {
         assert!(ocr_test(output, letter_forms));
     }
 
+    #[test]
+    fn lenient_recovers_bit_flip() {
+        // 'A' from the height-6 font with the left pixel of its second row flipped off, padded
+        // with blank trailing columns so the image is wide enough for `image_dims` to infer the
+        // height.
+        let corrupted = r"
+.##.....
+...#....
+#..#....
+####....
+#..#....
+#..#....
+        ";
+        assert_eq!(ocr(corrupted), Some("?".to_string()));
+        assert_eq!(ocr_lenient(corrupted, 1), Some("A".to_string()));
+    }
+
+    #[test]
+    fn lenient_with_confidence_reports_distance() {
+        // Same single bit-flip as `lenient_recovers_bit_flip`, but checked through the
+        // confidence-reporting entry point so the distance value itself is verified, not just
+        // the recovered letter.
+        let corrupted = r"
+.##.....
+...#....
+#..#....
+####....
+#..#....
+#..#....
+        ";
+        assert_eq!(ocr_lenient_with_confidence(corrupted, 1), Some(vec![('A', 1)]));
+    }
+
+    #[test]
+    fn lenient_respects_error_threshold() {
+        let corrupted = r"
+.##.....
+...#....
+#..#....
+####....
+#..#....
+#..#....
+        ";
+        assert_eq!(ocr_lenient(corrupted, 0), Some("?".to_string()));
+    }
+
+    #[test]
+    fn custom_font_set() {
+        let letter_forms = r"
+#.....##..#...##..#.###..
+#....#..#.#...##..#.#..#.
+#....#.....#.#.####.###..
+#....#.##...#..#..#.#..#.
+#....#..#...#..#..#.#..#.
+####..###...#..#..#.###..
+        ".trim();
+        let font = FontSetBuilder::new(6)
+            .fixed_width(5)
+            .register(letter_forms, "LGYHB")
+            .build();
+        assert_eq!(ocr_with(letter_forms, &font), Some("LGYHB".to_string()));
+    }
+
+    #[test]
+    fn detailed() {
+        let letter_forms = r"
+#.....##..#...##..#.###..
+#....#..#.#...##..#.#..#.
+#....#.....#.#.####.###..
+#....#.##...#..#..#.#..#.
+#....#..#...#..#..#.#..#.
+####..###...#..#..#.###..
+        ";
+        let glyphs = ocr_detailed(letter_forms).unwrap();
+        let chars: Vec<char> = glyphs.iter().map(|glyph| glyph.ch.unwrap()).collect();
+        assert_eq!(chars.as_slice(), ['L', 'G', 'Y', 'H', 'B'].as_slice());
+        assert!(glyphs.iter().all(|glyph| glyph.height == 6));
+        assert!(glyphs.iter().all(|glyph| glyph.x_end - glyph.x_start == glyph.width));
+        assert_eq!(glyphs[0].x_start, 0);
+    }
+
     #[test]
     fn bool_vec() {
         let output = "LGYHB";
@@ -1,5 +1,6 @@
-/// Marks a data type as compatible with the `ocr()` function. 
+use alloc::{string::{String, ToString}, vec::Vec};
 
+/// Marks a data type as compatible with the `ocr()` function.
 pub trait Scannable {
     
     /// Converts the type into a String that can be read by `ocr()`. 
@@ -28,7 +29,7 @@ impl Scannable for &str {
 impl Scannable for (&Vec<bool>, usize) {
     fn normalize(&self) -> String {
         let (bools, width) = self;
-        let width = width.clone();
+        let width = *width;
         let mut output = String::new();
         let height = bools.len() / width;
         for y in 0..height {
@@ -44,7 +45,7 @@ impl Scannable for (&Vec<bool>, usize) {
 impl Scannable for (&Vec<char>, usize) {
     fn normalize(&self) -> String {
         let (chars, width) = self;
-        let width = width.clone();
+        let width = *width;
         let mut output = String::new();
         let height = chars.len() / width;
         for y in 0..height {